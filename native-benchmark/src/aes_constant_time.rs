@@ -0,0 +1,239 @@
+//! 상수-시간(constant-time), 테이블 없는 AES-128-CTR.
+//!
+//! `process_secure_vector`가 수행하는 1바이트 XOR은 암호가 아니라 단순 마스킹이다.
+//! 이 모듈은 테이블 조회(S-box lookup)를 전혀 쓰지 않고 GF(2^8) 역원을 거듭제곱
+//! (`x^254`)으로 직접 계산해 SubBytes를 만든다 - 캐시 타이밍 부채널이 구조적으로
+//! 존재할 수 없다. 라운드 키는 16바이트 키로부터 한 번만 선산출(precompute)한다.
+//! AES-128-CTR은 `aes128_ctr_xor`로만 제공되며, `process_secure_vector`는 이 모듈을
+//! 호출하지 않고 여전히 1바이트 XOR만 수행한다.
+//!
+//! 주의: 상태를 비트 평면으로 전치해 AVX2 레인 8개에 블록을 병렬로 태우는
+//! bitslice 암호는 아니다. `encrypt_block`은 블록 하나씩 순차적으로 암호화하는
+//! 스칼라 구현이고, AVX2는 키스트림을 버퍼에 적용하는 마지막 XOR 단계(`xor_block_avx2`)
+//! 에만 쓰인다. 8블록 단위 비트슬라이스 병렬화는 하지 않는다.
+
+use std::arch::x86_64::{_mm256_loadu_si256, _mm256_storeu_si256, _mm256_xor_si256, __m256i};
+use std::slice;
+
+const RCON: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1B, 0x36];
+
+/// GF(2^8)에서 AES 기약다항식(x^8 + x^4 + x^3 + x + 1, 0x11B)에 대한 곱셈.
+/// 분기 없이(branchless) 비트마다 xtime을 반복 적용한다.
+fn gf256_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut result = 0u8;
+    for _ in 0..8 {
+        let mask = 0u8.wrapping_sub(b & 1);
+        result ^= a & mask;
+        b >>= 1;
+        a = xtime(a);
+    }
+    result
+}
+
+/// GF(2^8) 원소를 2배(x 곱)한다. 최상위 비트가 서면 기약다항식 0x1B를 XOR하는데,
+/// 이 조건부 XOR을 분기 없이 마스크 연산으로 구현한다(MixColumns의 branchless 요구사항과 동일).
+fn xtime(x: u8) -> u8 {
+    let carry_mask = 0u8.wrapping_sub((x >> 7) & 1);
+    (x << 1) ^ (carry_mask & 0x1B)
+}
+
+/// `x^254 = x^-1` (x != 0), `0^254 = 0`. 테이블 없이 제곱-후-곱(square-and-multiply)만으로
+/// GF(2^8) 곱셈 역원을 구하는 조합 회로에 해당한다. 지수(254, 공개 상수)의 비트만 제어
+/// 흐름에 관여하므로 비밀 입력 `x`에 대해 실행 경로가 항상 동일하다.
+fn gf256_inv(x: u8) -> u8 {
+    let mut base = x;
+    let mut result = 1u8;
+    let mut exponent = 254u8;
+    for _ in 0..8 {
+        if exponent & 1 == 1 {
+            result = gf256_mul(result, base);
+        }
+        base = gf256_mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Rijndael S-box의 아핀 변환 절반: `b' = b ^ rotl(b,1) ^ rotl(b,2) ^ rotl(b,3) ^ rotl(b,4) ^ 0x63`.
+fn affine(b: u8) -> u8 {
+    let rotl = |v: u8, n: u32| v.rotate_left(n);
+    b ^ rotl(b, 1) ^ rotl(b, 2) ^ rotl(b, 3) ^ rotl(b, 4) ^ 0x63
+}
+
+/// 테이블 없이 계산되는 S-box: GF(2^8) 역원 + 아핀 변환.
+fn sbox(x: u8) -> u8 {
+    affine(gf256_inv(x))
+}
+
+/// 16바이트 키로부터 AES-128의 11개 라운드 키(176바이트)를 한 번만 펼친다.
+fn key_schedule(key: &[u8; 16]) -> [[u8; 16]; 11] {
+    let mut w = [[0u8; 4]; 44];
+    for i in 0..4 {
+        w[i] = [key[4 * i], key[4 * i + 1], key[4 * i + 2], key[4 * i + 3]];
+    }
+
+    for i in 4..44 {
+        let mut temp = w[i - 1];
+        if i % 4 == 0 {
+            // RotWord + SubWord + Rcon
+            temp = [temp[1], temp[2], temp[3], temp[0]];
+            temp = [sbox(temp[0]), sbox(temp[1]), sbox(temp[2]), sbox(temp[3])];
+            temp[0] ^= RCON[i / 4 - 1];
+        }
+        w[i] = [
+            w[i - 4][0] ^ temp[0],
+            w[i - 4][1] ^ temp[1],
+            w[i - 4][2] ^ temp[2],
+            w[i - 4][3] ^ temp[3],
+        ];
+    }
+
+    let mut round_keys = [[0u8; 16]; 11];
+    for r in 0..11 {
+        for c in 0..4 {
+            round_keys[r][4 * c..4 * c + 4].copy_from_slice(&w[4 * r + c]);
+        }
+    }
+    round_keys
+}
+
+fn add_round_key(state: &mut [u8; 16], round_key: &[u8; 16]) {
+    for i in 0..16 {
+        state[i] ^= round_key[i];
+    }
+}
+
+fn sub_bytes(state: &mut [u8; 16]) {
+    for byte in state.iter_mut() {
+        *byte = sbox(*byte);
+    }
+}
+
+/// 상태는 열(column)-우선 배치: `state[row + 4*col]`. 행 r은 왼쪽으로 r칸 순환 이동한다.
+fn shift_rows(state: &mut [u8; 16]) {
+    let s = *state;
+    for row in 1..4 {
+        for col in 0..4 {
+            state[row + 4 * col] = s[row + 4 * ((col + row) % 4)];
+        }
+    }
+}
+
+/// 고정 행렬 `[2 3 1 1; 1 2 3 1; 1 1 2 3; 3 1 1 2]`을 각 열에 곱한다.
+/// 곱셈은 전부 `xtime`(2배)과 XOR(3배 = xtime(x) ^ x)만으로 구성되어 분기가 없다.
+fn mix_columns(state: &mut [u8; 16]) {
+    for c in 0..4 {
+        let a0 = state[4 * c];
+        let a1 = state[4 * c + 1];
+        let a2 = state[4 * c + 2];
+        let a3 = state[4 * c + 3];
+
+        state[4 * c] = xtime(a0) ^ (xtime(a1) ^ a1) ^ a2 ^ a3;
+        state[4 * c + 1] = a0 ^ xtime(a1) ^ (xtime(a2) ^ a2) ^ a3;
+        state[4 * c + 2] = a0 ^ a1 ^ xtime(a2) ^ (xtime(a3) ^ a3);
+        state[4 * c + 3] = (xtime(a0) ^ a0) ^ a1 ^ a2 ^ xtime(a3);
+    }
+}
+
+/// 단일 16바이트 블록을 AES-128로 암호화해 키스트림 블록을 만든다(CTR 모드이므로 복호화도 동일).
+fn encrypt_block(input: &[u8; 16], round_keys: &[[u8; 16]; 11]) -> [u8; 16] {
+    let mut state = *input;
+    add_round_key(&mut state, &round_keys[0]);
+
+    for round in &round_keys[1..10] {
+        sub_bytes(&mut state);
+        shift_rows(&mut state);
+        mix_columns(&mut state);
+        add_round_key(&mut state, round);
+    }
+
+    sub_bytes(&mut state);
+    shift_rows(&mut state);
+    add_round_key(&mut state, &round_keys[10]);
+    state
+}
+
+/// 128비트 카운터(빅엔디안)를 1 증가시킨다.
+fn increment_counter(counter: &mut [u8; 16]) {
+    for byte in counter.iter_mut().rev() {
+        *byte = byte.wrapping_add(1);
+        if *byte != 0 {
+            break;
+        }
+    }
+}
+
+/// `ptr..ptr+len` 버퍼에 AES-128-CTR 키스트림을 제자리(in-place) XOR한다.
+/// 라운드 키는 호출당 한 번만 펼치고, 8블록(128바이트) 단위로 키스트림을 모아
+/// AVX2 32바이트 XOR로 데이터에 적용한다(레인 수만큼 `process_vector_avx2`와 동일한 패턴).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn aes128_ctr_xor(
+    ptr: *mut u8,
+    len: usize,
+    key: *const u8,
+    nonce: *const u8,
+) {
+    if ptr.is_null() || key.is_null() || nonce.is_null() || len == 0 {
+        return;
+    }
+
+    let mut key_bytes = [0u8; 16];
+    key_bytes.copy_from_slice(slice::from_raw_parts(key, 16));
+    let round_keys = key_schedule(&key_bytes);
+
+    let mut counter = [0u8; 16];
+    counter.copy_from_slice(slice::from_raw_parts(nonce, 16));
+
+    let data = slice::from_raw_parts_mut(ptr, len);
+    let use_avx2 = is_x86_feature_detected!("avx2");
+
+    let mut offset = 0usize;
+    let mut keystream_batch = [0u8; 128]; // 8블록치 키스트림 스테이징 버퍼
+
+    while offset + 128 <= data.len() {
+        for block in 0..8 {
+            let ks = encrypt_block(&counter, &round_keys);
+            keystream_batch[block * 16..block * 16 + 16].copy_from_slice(&ks);
+            increment_counter(&mut counter);
+        }
+
+        if use_avx2 {
+            xor_block_avx2(&mut data[offset..offset + 128], &keystream_batch);
+        } else {
+            for i in 0..128 {
+                data[offset + i] ^= keystream_batch[i];
+            }
+        }
+
+        offset += 128;
+    }
+
+    // 128바이트 미만으로 남은 블록들은 블록 단위 스칼라 XOR로 마무리한다.
+    while offset < data.len() {
+        let ks = encrypt_block(&counter, &round_keys);
+        increment_counter(&mut counter);
+
+        let block_len = (data.len() - offset).min(16);
+        for i in 0..block_len {
+            data[offset + i] ^= ks[i];
+        }
+        offset += block_len;
+    }
+}
+
+/// 128바이트(8블록) 키스트림을 AVX2 32바이트 단위로 XOR 적용한다.
+#[target_feature(enable = "avx2")]
+unsafe fn xor_block_avx2(data: &mut [u8], keystream: &[u8; 128]) {
+    let mut i = 0;
+    while i + 32 <= data.len() {
+        let d = _mm256_loadu_si256(data.as_ptr().add(i) as *const __m256i);
+        let k = _mm256_loadu_si256(keystream.as_ptr().add(i) as *const __m256i);
+        let xored = _mm256_xor_si256(d, k);
+        _mm256_storeu_si256(data.as_mut_ptr().add(i) as *mut __m256i, xored);
+        i += 32;
+    }
+    while i < data.len() {
+        data[i] ^= keystream[i];
+        i += 1;
+    }
+}
@@ -1,9 +1,20 @@
+mod aes_constant_time;
+
 use jni::sys::*;
 use std::arch::x86_64::{
-    __m256i, _mm256_add_epi32, _mm256_and_si256, _mm256_cmpgt_epi32, _mm256_loadu_si256,
-    _mm256_set1_epi32, _mm256_set1_epi8, _mm256_storeu_si256, _mm256_sub_epi32, _mm256_xor_si256,
+    __m256i, _mm256_add_epi32, _mm256_add_epi64, _mm256_and_si256, _mm256_andnot_si256,
+    _mm256_castps_si256, _mm256_castsi256_ps, _mm256_cmpeq_epi32, _mm256_cmpgt_epi32,
+    _mm256_cvtepi32_ps, _mm256_extracti128_si256, _mm256_loadu_si256, _mm256_madd_epi16,
+    _mm256_maddubs_epi16, _mm256_movemask_ps, _mm256_mul_epu32, _mm256_or_si256,
+    _mm256_permute2x128_si256, _mm256_permute4x64_epi64, _mm256_permutevar8x32_epi32,
+    _mm256_set1_epi16, _mm256_set1_epi32, _mm256_set1_epi64x, _mm256_set1_epi8, _mm256_set_epi32,
+    _mm256_set_epi64x, _mm256_set_epi8, _mm256_shuffle_epi8, _mm256_slli_epi64,
+    _mm256_sllv_epi32, _mm256_srli_epi32, _mm256_srli_epi64, _mm256_srlv_epi32,
+    _mm256_storeu_si256, _mm256_sub_epi32, _mm256_unpackhi_epi8, _mm256_unpacklo_epi8,
+    _mm256_xor_si256, _mm_add_epi32, _mm_cvtsi128_si32, _mm_shuffle_epi32,
 };
 use std::slice;
+use std::sync::OnceLock;
 
 /// ffm api 단순 ++
 #[unsafe(no_mangle)]
@@ -220,3 +231,704 @@ pub unsafe extern "C" fn poly_add_avx2(a_ptr: *mut i32, b_ptr: *const i32, len:
 //
 // AVX - end
 //
+
+//
+// RNG - start
+//
+
+/// 64비트 레인마다 `state * mult`의 하위 64비트를 계산하는 내부 헬퍼로,
+/// PCG(Permuted Congruential Generator) 상태 전진(`pcg_next4_avx2`)의 곱셈 단계에 쓰인다.
+/// AVX2에는 64비트 정수 곱셈이 없으므로 `_mm256_mul_epu32` 3회로 하위 64비트 곱을 흉내낸다.
+/// lo(state) * lo(mult) + (hi(state) * lo(mult) + lo(state) * hi(mult)) << 32
+#[target_feature(enable = "avx2")]
+unsafe fn pcg_mul64_emulated(state: __m256i, mult: __m256i) -> __m256i {
+    // 32비트 절반들 (각 64비트 레인의 하위 32비트만 사용하는 `_mm256_mul_epu32` 규약에 맞춤)
+    let state_hi = _mm256_srli_epi64(state, 32);
+    let mult_hi = _mm256_srli_epi64(mult, 32);
+
+    let lo_lo = _mm256_mul_epu32(state, mult); // state_lo * mult_lo (64비트 결과, 하위 32비트가 겹칠 수 있음)
+    let hi_lo = _mm256_mul_epu32(state_hi, mult); // state_hi * mult_lo
+    let lo_hi = _mm256_mul_epu32(state, mult_hi); // state_lo * mult_hi
+
+    let cross = _mm256_add_epi64(hi_lo, lo_hi);
+    let cross_shifted = _mm256_slli_epi64(cross, 32);
+
+    _mm256_add_epi64(lo_lo, cross_shifted)
+}
+
+/// 4개의 독립적인 PCG 스트림을 한 스텝 전진시키고, 각 레인의 xsh-rr 32비트 출력을 만든다.
+/// `rot = state >> 59`, `xsh = (((state >> 18) ^ state) >> 27) as u32`,
+/// `output = xsh.rotate_right(rot)`.
+#[target_feature(enable = "avx2")]
+unsafe fn pcg_next4_avx2(states: &mut __m256i, mult: __m256i, increments: __m256i) -> __m256i {
+    // LCG 전진: state = state * mult + increment
+    let advanced = _mm256_add_epi64(pcg_mul64_emulated(*states, mult), increments);
+    *states = advanced;
+
+    // xsh-rr 퍼뮤테이션 (32비트 출력이 각 64비트 레인의 하위 32비트에 담김)
+    let xsh64 = _mm256_xor_si256(_mm256_srli_epi64(advanced, 18), advanced);
+    let xsh64 = _mm256_srli_epi64(xsh64, 27);
+
+    // 레인별 회전량 (0..=31), rotate_right(rot) = (xsh >> rot) | (xsh << (32 - rot))
+    let rot = _mm256_srli_epi64(advanced, 59);
+    // `_mm256_srlv_epi32`/`_mm256_sllv_epi32`는 32비트 레인 단위 변량 시프트이므로,
+    // 64비트 레인에 들어 있는 값을 그대로 하위 32비트 레인으로 취급해도 상위 32비트는 0이라 안전하다.
+    let rot32 = rot; // 상위 32비트는 이미 0 (state >> 59는 5비트 이내)
+    let inv_rot32 = _mm256_sub_epi32(_mm256_set1_epi32(32), rot32);
+
+    let right = _mm256_srlv_epi32(xsh64, rot32);
+    let left = _mm256_sllv_epi32(xsh64, inv_rot32);
+    _mm256_or_si256(right, left)
+}
+
+/// PCG(Lcg64Xsh32) 기반 SIMD 균등분포 다항식 계수 생성기.
+/// `[0, q)` 범위의 균등 난수를 4개 레인 병렬 스트림으로 생성하고, 모듈로 편향을 피하기 위해
+/// `floor((2^32-1)/q)*q`(= `u32::MAX / q * q`) 이상의 출력은 기각(rejection sampling)한다.
+/// 이 경계는 q의 배수라 편향이 없지만, q가 2의 거듭제곱이면 `floor(2^32/q)*q`보다 q만큼
+/// 작아서 편향과 무관한 최상위 q개 출력도 기각된다.
+/// 키/노이즈 생성을 JVM 왕복 없이 네이티브에서 수행할 수 있게 해준다.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fill_uniform_poly_avx2(out_ptr: *mut i32, len: usize, q: i32, seed: u64) {
+    if out_ptr.is_null() || len == 0 || q <= 0 {
+        return;
+    }
+
+    let out = slice::from_raw_parts_mut(out_ptr, len);
+    let q_u32 = q as u32;
+    // 기각 경계: 이 값 이상의 출력은 버려서 [0, q) 구간이 균등하게 매핑되도록 한다.
+    let limit = (u32::MAX / q_u32) * q_u32;
+
+    let mut written = 0usize;
+
+    if is_x86_feature_detected_avx2() {
+        written = fill_uniform_poly_avx2_inner(out, q_u32, limit, seed);
+    }
+
+    // 나머지(또는 AVX2 미지원 시 전체)는 스칼라 PCG32로 채운다.
+    fill_uniform_poly_scalar_tail(&mut out[written..], q_u32, limit, seed ^ 0x9E3779B97F4A7C15);
+}
+
+/// 런타임에 AVX2 지원 여부를 확인한다 (크레이트 전역에 감지 로직이 없어 로컬로 둔다).
+fn is_x86_feature_detected_avx2() -> bool {
+    is_x86_feature_detected!("avx2")
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn fill_uniform_poly_avx2_body(out: &mut [i32], q: u32, limit: u32, seed: u64) -> usize {
+    const MULT: i64 = 6364136223846793005u64 as i64;
+
+    // 레인별로 서로 다른 홀수 증분(increment)을 사용해 독립적인 스트림을 만든다.
+    let mult = _mm256_set1_epi64x(MULT);
+    let increments = _mm256_set_epi64x(
+        ((seed ^ 0xDA3E_39CB_94B9_5BDBu64) | 1) as i64,
+        ((seed ^ 0x1234_5678_9ABC_DEF1u64) | 1) as i64,
+        ((seed ^ 0x5851_F42D_4C95_7F2Du64) | 1) as i64,
+        (seed | 1) as i64,
+    );
+    let mut states = _mm256_set_epi64x(
+        (seed ^ 0xBB67_AE85_84CA_A73Bu64) as i64,
+        (seed ^ 0x6A09_E667_F3BC_C908u64) as i64,
+        (seed ^ 0x3C6E_F372_FE94_F82Bu64) as i64,
+        seed as i64,
+    );
+
+    // `pcg_next4_avx2`의 출력은 4개의 64비트 레인에 담겨 있고, 실제 32비트 결과는 각 레인의
+    // 하위 32비트에만 있다(상위 32비트에는 출력과 무관한 `state >> 59` 값이 남는다). 그래서 8개 u32 슬롯 전체를 저장한 뒤
+    // 짝수 인덱스(0,2,4,6)만 실제 RNG 출력으로 취급한다.
+    let mut lanes = [0u32; 8];
+    let mut written = 0usize;
+
+    while written + 4 <= out.len() {
+        let outputs = pcg_next4_avx2(&mut states, mult, increments);
+        std::arch::x86_64::_mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, outputs);
+
+        for &lane in lanes.iter().step_by(2) {
+            if written >= out.len() {
+                break;
+            }
+            if lane < limit {
+                out[written] = (lane % q) as i32;
+                written += 1;
+            }
+            // 기각된 레인은 건너뛰고, 다음 4-레인 배치에서 다시 시도한다.
+        }
+    }
+
+    written
+}
+
+unsafe fn fill_uniform_poly_avx2_inner(out: &mut [i32], q: u32, limit: u32, seed: u64) -> usize {
+    fill_uniform_poly_avx2_body(out, q, limit, seed)
+}
+
+/// 스칼라 PCG32(Lcg64Xsh32) 꼬리 처리. AVX2 레인 수(4)로 나누어떨어지지 않는 나머지나
+/// AVX2 미지원 환경을 담당한다.
+fn fill_uniform_poly_scalar_tail(out: &mut [i32], q: u32, limit: u32, seed: u64) {
+    let mut state = seed;
+    let increment = (seed ^ 0x1234_5678_9ABC_DEF1u64) | 1;
+
+    let mut i = 0;
+    while i < out.len() {
+        state = state.wrapping_mul(6364136223846793005u64).wrapping_add(increment);
+        let rot = (state >> 59) as u32;
+        let xsh = (((state >> 18) ^ state) >> 27) as u32;
+        let output = xsh.rotate_right(rot);
+
+        if output < limit {
+            out[i] = (output % q) as i32;
+            i += 1;
+        }
+    }
+}
+
+//
+// RNG - end
+//
+
+//
+// CHECKSUM - start
+//
+
+/// Adler32 모듈로 상수와, 덧셈을 지연(defer)시킬 수 있는 최대 블록 크기.
+/// `255*NMAX*(NMAX+1)/2 + (NMAX+1)*65520`이 u32 범위를 넘지 않는 가장 큰 n.
+const ADLER_MOD: u32 = 65521;
+const ADLER_NMAX: usize = 5552;
+
+/// `process_secure_vector` / `process_vector_avx2`가 MemorySegment를 변환한 뒤, 버퍼가
+/// 끝까지 제대로 변환되었는지 Java 쪽에서 검증할 수 있도록 하는 Adler32 체크섬.
+/// NMAX 바이트 블록 단위로 모듈로 연산을 지연시키고, 블록 내부는 AVX2로 벡터화한다.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn adler32_avx2(ptr: *const u8, len: usize) -> u32 {
+    if ptr.is_null() || len == 0 {
+        return 1; // a=1, b=0 인 빈 버퍼의 Adler32 값
+    }
+
+    let data = slice::from_raw_parts(ptr, len);
+
+    if is_x86_feature_detected!("avx2") {
+        adler32_avx2_body(data)
+    } else {
+        adler32_scalar(data, 1, 0)
+    }
+}
+
+/// 블록(최대 NMAX 바이트) 단위로 AVX2 가속 처리 후, 블록 경계에서만 `% 65521`을 적용한다.
+#[target_feature(enable = "avx2")]
+unsafe fn adler32_avx2_body(data: &[u8]) -> u32 {
+    let mut a: u32 = 1;
+    let mut b: u32 = 0;
+
+    let mut chunks = data.chunks(ADLER_NMAX);
+    for chunk in &mut chunks {
+        let mut offset = 0usize;
+
+        while offset + 32 <= chunk.len() {
+            let bytes = _mm256_loadu_si256(chunk.as_ptr().add(offset) as *const __m256i);
+
+            // 내림차순 가중치 (32, 31, ..., 1)를 곱해 위치별 부분합을 만든다.
+            let ones = _mm256_set1_epi8(1);
+            let weights = _mm256_set_epi8(
+                1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
+                24, 25, 26, 27, 28, 29, 30, 31, 32,
+            );
+            let byte_sum = _mm256_maddubs_epi16(bytes, ones); // 인접 바이트쌍의 합 (16비트 레인)
+            let weighted = _mm256_maddubs_epi16(bytes, weights);
+            let weighted32 = _mm256_madd_epi16(weighted, _mm256_set1_epi16(1));
+            let sum32 = _mm256_madd_epi16(byte_sum, _mm256_set1_epi16(1));
+
+            b = b.wrapping_add(32u32.wrapping_mul(a));
+            a = a.wrapping_add(hsum_epi32_avx2(sum32));
+            b = b.wrapping_add(hsum_epi32_avx2(weighted32));
+
+            offset += 32;
+        }
+
+        // 32바이트 미만으로 남은 꼬리는 스칼라로 처리 (NMAX = 173*32 + 16이므로 온전한
+        // NMAX 블록마다 16바이트 꼬리가 생기고, 마지막 블록은 길이에 따라 달라진다)
+        for &byte in &chunk[offset..] {
+            a = a.wrapping_add(byte as u32);
+            b = b.wrapping_add(a);
+        }
+
+        a %= ADLER_MOD;
+        b %= ADLER_MOD;
+    }
+
+    (b << 16) | a
+}
+
+/// 32바이트 레인(8 x i32)을 수평 합산한다.
+#[target_feature(enable = "avx2")]
+unsafe fn hsum_epi32_avx2(v: __m256i) -> u32 {
+    let lo = _mm256_extracti128_si256(v, 0);
+    let hi = _mm256_extracti128_si256(v, 1);
+    let sum128 = _mm_add_epi32(lo, hi);
+    let shuf = _mm_shuffle_epi32(sum128, 0b00_01_10_11);
+    let sum64 = _mm_add_epi32(sum128, shuf);
+    let shuf2 = _mm_shuffle_epi32(sum64, 0b00_00_00_01);
+    let sum32 = _mm_add_epi32(sum64, shuf2);
+    _mm_cvtsi128_si32(sum32) as u32
+}
+
+/// 스칼라 Adler32 구현. AVX2 미지원 환경에서 쓰인다(AVX2 경로의 32바이트 미만 꼬리는
+/// `adler32_avx2_body` 안에서 직접 처리한다).
+fn adler32_scalar(data: &[u8], mut a: u32, mut b: u32) -> u32 {
+    for chunk in data.chunks(ADLER_NMAX) {
+        for &byte in chunk {
+            a = a.wrapping_add(byte as u32);
+            b = b.wrapping_add(a);
+        }
+        a %= ADLER_MOD;
+        b %= ADLER_MOD;
+    }
+    (b << 16) | a
+}
+
+//
+// CHECKSUM - end
+//
+
+//
+// SWAR - start
+//
+
+/// `poly_modular_add` / `bless_poly_modular_add`는 i32 레인의 모듈러 가산만 제공하고,
+/// NTT 버터플라이와 LWE 복호에 필요한 모듈러 감산은 없었다. 바이트 폭 계수(q <= 255)에 대해
+/// `swar_process_secure_vector`와 같은 8바이트(64비트) SWAR 방식으로 감산을 처리한다.
+/// 연산식: `a[i] = (a[i] - b[i] + q) mod q`
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn swar_poly_byte_sub(a_ptr: *mut u8, b_ptr: *const u8, len: usize, q: u8) {
+    if a_ptr.is_null() || b_ptr.is_null() {
+        return;
+    }
+
+    // 8바이트 정렬 맞추기 전까지 스칼라 처리
+    let mut offset = 0;
+    while (a_ptr.add(offset) as usize) % 8 != 0 && offset < len {
+        *a_ptr.add(offset) = byte_mod_sub(*a_ptr.add(offset), *b_ptr.add(offset), q);
+        offset += 1;
+    }
+
+    // 8바이트(64비트) 단위 SWAR 감산. `offset`이 8의 배수가 되지 못한 채(즉, len < 8이라
+    // 정렬 루프가 길이 끝에서 멈춘 채) 여기 도달할 수 있으므로, 8바이트가 온전히 남아 있을
+    // 때만 64비트 슬라이스를 구성한다 - 그렇지 않으면 정렬되지 않은 포인터로
+    // `slice::from_raw_parts_mut`를 호출하는 UB가 된다.
+    if offset + 8 <= len {
+        const H: u64 = 0x8080808080808080;
+        let q64 = u64::from_le_bytes([q; 8]);
+
+        let a_ptr64 = a_ptr.add(offset) as *mut u64;
+        let b_ptr64 = b_ptr.add(offset) as *const u64;
+        let len64 = (len - offset) / 8;
+
+        let a_words = slice::from_raw_parts_mut(a_ptr64, len64);
+        let b_words = slice::from_raw_parts(b_ptr64, len64);
+
+        for i in 0..len64 {
+            let a = a_words[i];
+            let b = b_words[i];
+
+            // Hacker's Delight 패킹된 바이트 감산: 바이트 경계를 넘는 borrow 전파를 차단한다.
+            let diff = ((a | H) - (b & !H)) ^ ((a ^ !b) & H);
+
+            // 실제 바이트별 underflow(unsigned a < b) 여부: `diff`의 최상위 비트만으로는
+            // "결과가 우연히 128 이상"인 경우와 구분되지 않으므로, 전가산기의 borrow-out을
+            // 그대로 계산하는 표준 SWAR 공식을 쓴다: borrow = (~a & b) | ((~a | b) & diff).
+            let borrow_mask = (!a & b) | ((!a | b) & diff);
+            // 최상위 비트만 서 있는 마스크를, 해당 바이트 전체를 덮는 0xFF 마스크로 퍼뜨린다.
+            let fix_mask = ((borrow_mask & H) >> 7).wrapping_mul(0xFF);
+
+            // `diff + q`를 바이트 경계를 넘지 않는 덧셈으로 적용한다(패킹된 가산의
+            // Hacker's Delight 짝꿍 트릭): 바이트마다 최상위 비트를 먼저 떼어내고 더하면
+            // 캐리가 절대 다음 바이트로 넘어가지 않는다.
+            let addend = q64 & fix_mask;
+            a_words[i] = ((diff & !H) + (addend & !H)) ^ ((diff ^ addend) & H);
+        }
+
+        offset += len64 * 8;
+    }
+
+    // 남은 자투리 바이트 처리
+    while offset < len {
+        *a_ptr.add(offset) = byte_mod_sub(*a_ptr.add(offset), *b_ptr.add(offset), q);
+        offset += 1;
+    }
+}
+
+/// 스칼라 바이트 모듈러 감산: `(a - b + q) mod q`. 정렬 프리픽스/테일 처리에 쓰인다.
+fn byte_mod_sub(a: u8, b: u8, q: u8) -> u8 {
+    let diff = a as i16 - b as i16;
+    (if diff < 0 { diff + q as i16 } else { diff }) as u8
+}
+
+#[cfg(test)]
+mod swar_poly_byte_sub_tests {
+    use super::swar_poly_byte_sub;
+
+    // 한 8바이트 워드 안에 borrow가 필요한 바이트와 필요 없는 바이트를 섞어, +q 보정이
+    // 이웃 바이트로 캐리를 흘리지 않는지 확인한다. 두 버퍼를 8바이트 정렬해 정렬 프리픽스
+    // 없이 SWAR 워드 경로를 타게 한다.
+    #[repr(align(8))]
+    struct Aligned8([u8; 8]);
+
+    #[test]
+    fn mixed_borrow_and_non_borrow_bytes_in_one_word() {
+        let q = 251u8;
+        let mut a = Aligned8([200u8, 10, 200, 10, 200, 10, 200, 10]);
+        let b = Aligned8([50u8, 200, 50, 200, 50, 200, 50, 200]);
+        let (a, b) = (&mut a.0, &b.0);
+
+        let expected: Vec<u8> = a
+            .iter()
+            .zip(b.iter())
+            .map(|(&a, &b)| {
+                let diff = a as i16 - b as i16;
+                (if diff < 0 { diff + q as i16 } else { diff }) as u8
+            })
+            .collect();
+
+        unsafe {
+            swar_poly_byte_sub(a.as_mut_ptr(), b.as_ptr(), a.len(), q);
+        }
+
+        assert_eq!(&a[..], &expected[..]);
+    }
+}
+
+//
+// SWAR - end
+//
+
+//
+// FILTER - start
+//
+
+/// 8비트 movemask -> 일치하는 레인 인덱스를 앞으로 모으는 셔플 퍼뮤테이션 테이블.
+/// `filter_lut()[m]`은 비트마스크 `m`에서 set된 비트 위치들을 낮은 인덱스부터 채운
+/// 8개의 레인 선택자다(나머지 칸은 0, 뒤쪽 overstore에서 버려진다).
+static FILTER_LUT: OnceLock<[[u32; 8]; 256]> = OnceLock::new();
+
+fn build_filter_lut() -> [[u32; 8]; 256] {
+    let mut table = [[0u32; 8]; 256];
+    for (mask, perm) in table.iter_mut().enumerate() {
+        let mut slot = 0usize;
+        for bit in 0..8 {
+            if (mask >> bit) & 1 == 1 {
+                perm[slot] = bit as u32;
+                slot += 1;
+            }
+        }
+    }
+    table
+}
+
+fn filter_lut() -> &'static [[u32; 8]; 256] {
+    FILTER_LUT.get_or_init(build_filter_lut)
+}
+
+/// `[lo, hi]` 구간에 속하는 계수들의 인덱스만 빽빽하게(densely) `out`에 모아 쓴다.
+/// LWE/NTT의 기각 샘플링이나 연산 후 범위를 벗어난 항목을 찾는 용도로 쓰인다.
+/// 표준적인 SIMD 좌측 압축(left-packing) 기법: 8레인씩 범위 마스크를 계산하고, 그 마스크에
+/// 대응하는 셔플 퍼뮤테이션으로 일치 인덱스를 앞으로 모은 뒤 `popcount(mask)`만큼만 커서를
+/// 전진시킨다. `out`은 오버스토어(최대 7개 레인의 쓰레기 값 겹쳐쓰기)를 허용할 여유 공간이
+/// 있어야 한다(`len + 7` 이상 권장).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn filter_range_avx2(
+    input: *const u32,
+    len: usize,
+    lo: u32,
+    hi: u32,
+    out: *mut u32,
+) -> usize {
+    if input.is_null() || out.is_null() {
+        return 0;
+    }
+
+    let data = slice::from_raw_parts(input, len);
+    let mut written = 0usize;
+    let mut i = 0usize;
+
+    if is_x86_feature_detected!("avx2") {
+        written = filter_range_avx2_body(data, lo, hi, out);
+        i = (len / 8) * 8;
+    }
+
+    // 8의 배수가 아닌 꼬리는 스칼라로 처리
+    while i < len {
+        if data[i] >= lo && data[i] <= hi {
+            *out.add(written) = i as u32;
+            written += 1;
+        }
+        i += 1;
+    }
+
+    written
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn filter_range_avx2_body(data: &[u32], lo: u32, hi: u32, out: *mut u32) -> usize {
+    let lut = filter_lut();
+
+    // AVX2에는 부호 없는 비교가 없으므로 0x80000000으로 바이어스해서 부호 있는 비교로 바꾼다.
+    let bias = _mm256_set1_epi32(i32::MIN);
+    let lo_biased = _mm256_set1_epi32((lo as i32) ^ i32::MIN);
+    let hi_biased = _mm256_set1_epi32((hi as i32) ^ i32::MIN);
+
+    let mut written = 0usize;
+    let mut i = 0usize;
+
+    while i + 8 <= data.len() {
+        let values = _mm256_loadu_si256(data.as_ptr().add(i) as *const __m256i);
+        let biased = _mm256_xor_si256(values, bias);
+
+        // data < lo 이거나 data > hi 이면 제외
+        let below_lo = _mm256_cmpgt_epi32(lo_biased, biased);
+        let above_hi = _mm256_cmpgt_epi32(biased, hi_biased);
+        let exclude = _mm256_or_si256(below_lo, above_hi);
+        let in_range = _mm256_xor_si256(exclude, _mm256_set1_epi32(-1));
+
+        let mask = _mm256_movemask_ps(_mm256_castsi256_ps(in_range)) as u8 as usize;
+        let popcount = (mask as u32).count_ones() as usize;
+
+        let base = i as i32;
+        let indices = _mm256_set_epi32(
+            base + 7,
+            base + 6,
+            base + 5,
+            base + 4,
+            base + 3,
+            base + 2,
+            base + 1,
+            base,
+        );
+
+        let perm = _mm256_loadu_si256(lut[mask].as_ptr() as *const __m256i);
+        let compacted = _mm256_permutevar8x32_epi32(indices, perm);
+
+        // 최대 7개 레인을 더 써도(overstore) 다음 반복에서 실제 일치 개수만큼만 커서가
+        // 전진하므로 그 값들은 곧 덮어써진다.
+        _mm256_storeu_si256(out.add(written) as *mut __m256i, compacted);
+        written += popcount;
+
+        i += 8;
+    }
+
+    written
+}
+
+//
+// FILTER - end
+//
+
+//
+// INTERLEAVE - start
+//
+
+/// 짝/홀 바이트를 lane-local로 묶는 셔플 마스크: 각 128비트 레인 안에서
+/// `[even0..even7, odd0..odd7]` 순서로 재배치한다. 디인터리빙의 1단계로 쓰인다.
+#[target_feature(enable = "avx2")]
+unsafe fn even_odd_shuffle_mask() -> __m256i {
+    _mm256_set_epi8(
+        15, 13, 11, 9, 7, 5, 3, 1, 14, 12, 10, 8, 6, 4, 2, 0, 15, 13, 11, 9, 7, 5, 3, 1, 14, 12,
+        10, 8, 6, 4, 2, 0,
+    )
+}
+
+/// NTT/복소수 계수 워크로드가 인터리브된 버퍼(`[a0,b0,a1,b1,...]`)를 짝/홀(혹은 실수부/허수부)
+/// 두 스트림으로 분리한다. 64바이트(32쌍) 단위로 처리: lane-local 셔플로 짝/홀을 모은 뒤,
+/// `permute4x64`로 64비트 블록을 재배열하고 `permute2x128`로 레인 경계를 넘어 합친다.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn deinterleave_u8_avx2(
+    mixed: *const u8,
+    len: usize,
+    out_a: *mut u8,
+    out_b: *mut u8,
+) {
+    if mixed.is_null() || out_a.is_null() || out_b.is_null() {
+        return;
+    }
+
+    let half_len = len / 2;
+    let mut i = 0usize;
+
+    if is_x86_feature_detected!("avx2") {
+        i = deinterleave_u8_avx2_body(mixed, half_len, out_a, out_b);
+    }
+
+    // 32바이트(64바이트 입력) 미만으로 남은 꼬리는 스칼라로 처리
+    while i < half_len {
+        *out_a.add(i) = *mixed.add(2 * i);
+        *out_b.add(i) = *mixed.add(2 * i + 1);
+        i += 1;
+    }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn deinterleave_u8_avx2_body(
+    mixed: *const u8,
+    half_len: usize,
+    out_a: *mut u8,
+    out_b: *mut u8,
+) -> usize {
+    let mask = even_odd_shuffle_mask();
+    let mut i = 0usize;
+
+    while i + 32 <= half_len {
+        let mixed0 = _mm256_loadu_si256(mixed.add(2 * i) as *const __m256i);
+        let mixed1 = _mm256_loadu_si256(mixed.add(2 * i + 32) as *const __m256i);
+
+        let s0 = _mm256_shuffle_epi8(mixed0, mask); // [a0-7,b0-7 | a8-15,b8-15] (레인별)
+        let s1 = _mm256_shuffle_epi8(mixed1, mask); // [a16-23,b16-23 | a24-31,b24-31]
+
+        // qword 순서를 (0,2,1,3)로 재배열해 "a" 절반과 "b" 절반을 레인 안에서 모은다.
+        let s0_perm = _mm256_permute4x64_epi64(s0, 0b11_01_10_00);
+        let s1_perm = _mm256_permute4x64_epi64(s1, 0b11_01_10_00);
+
+        let a_vec = _mm256_permute2x128_si256(s0_perm, s1_perm, 0x20);
+        let b_vec = _mm256_permute2x128_si256(s0_perm, s1_perm, 0x31);
+
+        _mm256_storeu_si256(out_a.add(i) as *mut __m256i, a_vec);
+        _mm256_storeu_si256(out_b.add(i) as *mut __m256i, b_vec);
+
+        i += 32;
+    }
+
+    i
+}
+
+/// `deinterleave_u8_avx2`의 역: 두 개의 분리된 스트림을 다시 `[a0,b0,a1,b1,...]` 순서로 합친다.
+/// `_mm256_unpacklo_epi8`/`_mm256_unpackhi_epi8`는 128비트 레인 내부에서만 인터리브하므로,
+/// `_mm256_permute2x128_si256`로 레인 경계를 보정한다.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn interleave_u8_avx2(
+    a: *const u8,
+    b: *const u8,
+    half_len: usize,
+    out: *mut u8,
+) {
+    if a.is_null() || b.is_null() || out.is_null() {
+        return;
+    }
+
+    let mut i = 0usize;
+
+    if is_x86_feature_detected!("avx2") {
+        i = interleave_u8_avx2_body(a, b, half_len, out);
+    }
+
+    while i < half_len {
+        *out.add(2 * i) = *a.add(i);
+        *out.add(2 * i + 1) = *b.add(i);
+        i += 1;
+    }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn interleave_u8_avx2_body(a: *const u8, b: *const u8, half_len: usize, out: *mut u8) -> usize {
+    let mut i = 0usize;
+
+    while i + 32 <= half_len {
+        let a_vec = _mm256_loadu_si256(a.add(i) as *const __m256i);
+        let b_vec = _mm256_loadu_si256(b.add(i) as *const __m256i);
+
+        let lo = _mm256_unpacklo_epi8(a_vec, b_vec); // lane-local 인터리브 (레인0 저/레인1 저)
+        let hi = _mm256_unpackhi_epi8(a_vec, b_vec); // lane-local 인터리브 (레인0 고/레인1 고)
+
+        // AVX2 unpack의 레인-로컬 특성을 보정: 레인0끼리, 레인1끼리 다시 묶는다.
+        let out_lo = _mm256_permute2x128_si256(lo, hi, 0x20);
+        let out_hi = _mm256_permute2x128_si256(lo, hi, 0x31);
+
+        _mm256_storeu_si256(out.add(2 * i) as *mut __m256i, out_lo);
+        _mm256_storeu_si256(out.add(2 * i + 32) as *mut __m256i, out_hi);
+
+        i += 32;
+    }
+
+    i
+}
+
+//
+// INTERLEAVE - end
+//
+
+//
+// BITWIDTH - start
+//
+
+/// 다항식 계수의 가변 길이(비트 패킹) 직렬화를 위해, 각 32비트 원소의 유효 비트 수
+/// (bit-scan-reverse + 1, 0이면 0)를 8레인씩 계산해 `out`에 u8로 쓴다.
+/// AVX2에는 레인별 `lzcnt`가 없으므로, 정수를 f32로 변환했을 때 IEEE-754 지수부가
+/// bit-scan-reverse 인덱스와 같아지는 성질을 이용한다. 향후 비트 패킹 직렬화의 기반 연산이다.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bitwidth_i32_avx2(ptr: *const i32, len: usize, out: *mut u8) {
+    if ptr.is_null() || out.is_null() {
+        return;
+    }
+
+    let mut i = 0usize;
+
+    if is_x86_feature_detected!("avx2") {
+        i = bitwidth_i32_avx2_body(ptr, len, out);
+    }
+
+    while i < len {
+        let x = *ptr.add(i) as u32;
+        *out.add(i) = (32 - x.leading_zeros()) as u8;
+        i += 1;
+    }
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn bitwidth_i32_avx2_body(ptr: *const i32, len: usize, out: *mut u8) -> usize {
+    // 2^24 이상인 값은 f32(24비트 가수)로 변환할 때 반올림되어 지수가 한 칸 밀릴 수 있으므로,
+    // 비트 스캔 위치에 영향을 주지 않는 하위 8비트를 미리 지워 정확한 변환을 보장한다.
+    let bias = _mm256_set1_epi32(i32::MIN);
+    let threshold_biased = _mm256_set1_epi32(((1i32 << 24) - 1) ^ i32::MIN);
+    let clear_mask = _mm256_set1_epi32(!0xFFi32);
+    let zero = _mm256_set1_epi32(0);
+
+    let mut i = 0usize;
+    let mut lanes = [0i32; 8];
+
+    while i + 8 <= len {
+        let x = _mm256_loadu_si256(ptr.add(i) as *const __m256i);
+
+        let x_biased = _mm256_xor_si256(x, bias);
+        let needs_mask = _mm256_cmpgt_epi32(x_biased, threshold_biased);
+
+        let cleared = _mm256_and_si256(x, clear_mask);
+        let diff = _mm256_xor_si256(x, cleared);
+        let masked_diff = _mm256_and_si256(needs_mask, diff);
+        let x_adj = _mm256_xor_si256(x, masked_diff);
+
+        let as_float = _mm256_cvtepi32_ps(x_adj);
+        let bits = _mm256_castps_si256(as_float);
+        let exponent = _mm256_sub_epi32(_mm256_srli_epi32(bits, 23), _mm256_set1_epi32(127));
+        let width = _mm256_add_epi32(exponent, _mm256_set1_epi32(1));
+
+        let is_zero = _mm256_cmpeq_epi32(x, zero);
+        let width = _mm256_andnot_si256(is_zero, width);
+
+        // 최상위 비트가 선 값(부호 있는 표현에서는 음수)은 `_mm256_cvtepi32_ps`가 부호 있는
+        // 변환이라 지수가 틀어지므로, 항상 유효 비트 수 32로 덮어쓴다(스칼라 경로의
+        // `x as u32` 처리와 동일한 부호 없는 의미를 맞추기 위함).
+        let is_negative = _mm256_cmpgt_epi32(zero, x);
+        let width = _mm256_or_si256(
+            _mm256_and_si256(is_negative, _mm256_set1_epi32(32)),
+            _mm256_andnot_si256(is_negative, width),
+        );
+
+        _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, width);
+        for (j, &lane) in lanes.iter().enumerate() {
+            *out.add(i + j) = lane as u8;
+        }
+
+        i += 8;
+    }
+
+    i
+}
+
+//
+// BITWIDTH - end
+//